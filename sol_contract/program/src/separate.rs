@@ -1,6 +1,6 @@
 use anchor_lang::{
     prelude::*,
-    solana_program::program_pack::Pack, __private::CLOSED_ACCOUNT_DISCRIMINATOR,
+    solana_program::program_pack::{IsInitialized, Pack}, __private::CLOSED_ACCOUNT_DISCRIMINATOR,
     __private::ErrorCode::AccountDidNotSerialize
 };
 use std::io::Write;
@@ -14,6 +14,7 @@ use {
             pubkey::Pubkey,
             rent::Rent,
             system_instruction,
+            system_program,
         },
     },
 };
@@ -22,16 +23,38 @@ use {
 pub struct TokenTransferParams<'a: 'b, 'b> {
     /// CHECK: source
     pub source: AccountInfo<'a>,
+    /// CHECK: mint
+    pub mint: AccountInfo<'a>,
     /// CHECK: destination
     pub destination: AccountInfo<'a>,
     /// amount
     pub amount: u64,
+    /// decimals
+    pub decimals: u8,
+    /// fee
+    pub fee: u64,
+    /// authority
+    pub authority: AccountInfo<'a>,
+    /// authority_signer_seeds
+    pub authority_signer_seeds: &'b [&'b [u8]],
+    /// token_program
+    pub token_program: AccountInfo<'a>,
+    /// verify
+    pub verify: bool,
+}
+
+///TokenTransferBatchParams
+pub struct TokenTransferBatchParams<'a: 'b, 'b> {
+    /// CHECK: source
+    pub source: AccountInfo<'a>,
     /// authority
     pub authority: AccountInfo<'a>,
     /// authority_signer_seeds
     pub authority_signer_seeds: &'b [&'b [u8]],
     /// token_program
     pub token_program: AccountInfo<'a>,
+    /// (destination, amount) pairs, one per transfer leg
+    pub legs: &'b [(AccountInfo<'a>, u64)],
 }
 
 ///TokenMintParams
@@ -48,6 +71,26 @@ pub struct TokenMintParams<'a: 'b, 'b> {
     pub owner_signer_seeds: &'b [&'b [u8]],
     /// token_program
     pub token_program: AccountInfo<'a>,
+    /// verify
+    pub verify: bool,
+}
+
+///BurnParams
+pub struct BurnParams<'a: 'b, 'b> {
+    /// mint
+    pub mint: AccountInfo<'a>,
+    /// from
+    pub from: AccountInfo<'a>,
+    /// amount
+    pub amount: u64,
+    /// authority
+    pub authority: AccountInfo<'a>,
+    /// authority_signer_seeds
+    pub authority_signer_seeds: &'b [&'b [u8]],
+    /// token_program
+    pub token_program: AccountInfo<'a>,
+    /// verify
+    pub verify: bool,
 }
 
 ///InitializeTokenAccount
@@ -84,6 +127,8 @@ pub struct SetAuthorityParams<'a: 'b, 'b> {
     pub owner_signer_seeds: &'b [&'b [u8]],
     /// token_program
     pub token_program: AccountInfo<'a>,
+    /// verify
+    pub verify: bool,
 }
 
 ///CloseAccountParams
@@ -98,6 +143,8 @@ pub struct CloseAccountParams<'a: 'b, 'b> {
     pub owner_signer_seeds: &'b [&'b [u8]],
     /// token_program
     pub token_program: AccountInfo<'a>,
+    /// verify
+    pub verify: bool,
 }
 
 pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult {
@@ -108,8 +155,16 @@ pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult
         token_program,
         amount,
         authority_signer_seeds,
+        verify,
+        ..
     } = params;
 
+    if verify {
+        guards::assert_owned_by(&source, token_program.key)?;
+        guards::assert_owned_by(&destination, token_program.key)?;
+        guards::assert_initialized::<spl_token::state::Account>(&source)?;
+    }
+
     let result = invoke_signed(
         &spl_token::instruction::transfer(
             token_program.key,
@@ -126,6 +181,134 @@ pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult
     result.map_err(|_| ErrorCode::TokenTransferFailed.into())
 }
 
+pub fn spl_token_transfer_checked(params: TokenTransferParams<'_, '_>) -> ProgramResult {
+    let TokenTransferParams {
+        source,
+        mint,
+        destination,
+        authority,
+        token_program,
+        amount,
+        decimals,
+        authority_signer_seeds,
+        verify,
+        ..
+    } = params;
+
+    if verify {
+        guards::assert_owned_by(&source, token_program.key)?;
+        guards::assert_owned_by(&destination, token_program.key)?;
+        guards::assert_initialized::<spl_token::state::Account>(&source)?;
+    }
+
+    let result = invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[source, mint, destination, authority, token_program],
+        &[authority_signer_seeds],
+    );
+
+    result.map_err(|_| ErrorCode::TokenTransferFailed.into())
+}
+
+pub fn spl_token_transfer_checked_with_fee(params: TokenTransferParams<'_, '_>) -> ProgramResult {
+    let TokenTransferParams {
+        source,
+        mint,
+        destination,
+        authority,
+        token_program,
+        amount,
+        decimals,
+        fee,
+        authority_signer_seeds,
+        verify,
+    } = params;
+
+    if verify {
+        guards::assert_owned_by(&source, token_program.key)?;
+        guards::assert_owned_by(&destination, token_program.key)?;
+        guards::assert_initialized::<spl_token::state::Account>(&source)?;
+    }
+
+    let result = invoke_signed(
+        &spl_token_2022::instruction::transfer_checked_with_fee(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+            fee,
+        )?,
+        &[source, mint, destination, authority, token_program],
+        &[authority_signer_seeds],
+    );
+
+    result.map_err(|_| ErrorCode::TokenTransferFailed.into())
+}
+
+pub fn spl_token_transfer_batch(params: TokenTransferBatchParams<'_, '_>) -> Result<u64> {
+    let TokenTransferBatchParams {
+        source,
+        authority,
+        authority_signer_seeds,
+        token_program,
+        legs,
+    } = params;
+
+    let mut total: u64 = 0;
+    for (_, amount) in legs {
+        total = total
+            .checked_add(*amount)
+            .ok_or(ErrorCode::BatchTransferFailed)?;
+    }
+
+    let source_account = guards::assert_initialized::<spl_token::state::Account>(&source)?;
+    if source_account.amount < total {
+        return Err(ErrorCode::BatchTransferFailed.into());
+    }
+
+    for (index, (destination, amount)) in legs.iter().enumerate() {
+        let result = invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                source.key,
+                destination.key,
+                authority.key,
+                &[],
+                *amount,
+            )?,
+            &[
+                source.clone(),
+                destination.clone(),
+                authority.clone(),
+                token_program.clone(),
+            ],
+            &[authority_signer_seeds],
+        );
+
+        if result.is_err() {
+            // Anchor's #[error_code] variants can't carry data, so the failing leg's
+            // index is only recoverable from this log line, not from the error itself.
+            msg!("spl_token_transfer_batch: leg {} failed", index);
+            return Err(ErrorCode::BatchTransferFailed.into());
+        }
+    }
+
+    Ok(legs.len() as u64)
+}
+
 pub fn spl_token_mint(params: TokenMintParams<'_, '_>) -> ProgramResult {
     let TokenMintParams {
         mint,
@@ -134,8 +317,14 @@ pub fn spl_token_mint(params: TokenMintParams<'_, '_>) -> ProgramResult {
         owner,
         owner_signer_seeds,
         token_program,
+        verify,
     } = params;
 
+    if verify {
+        guards::assert_owned_by(&mint, token_program.key)?;
+        guards::assert_owned_by(&to, token_program.key)?;
+    }
+
     let result = invoke_signed(
         &spl_token::instruction::mint_to(
             token_program.key,
@@ -152,6 +341,38 @@ pub fn spl_token_mint(params: TokenMintParams<'_, '_>) -> ProgramResult {
     result.map_err(|_| ErrorCode::TokenMintFailed.into())
 }
 
+pub fn spl_token_burn(params: BurnParams<'_, '_>) -> ProgramResult {
+    let BurnParams {
+        mint,
+        from,
+        amount,
+        authority,
+        authority_signer_seeds,
+        token_program,
+        verify,
+    } = params;
+
+    if verify {
+        guards::assert_owned_by(&from, token_program.key)?;
+        guards::assert_initialized::<spl_token::state::Account>(&from)?;
+    }
+
+    let result = invoke_signed(
+        &spl_token::instruction::burn(
+            token_program.key,
+            from.key,
+            mint.key,
+            authority.key,
+            &[],
+            amount,
+        )?,
+        &[from, mint, authority, token_program],
+        &[authority_signer_seeds],
+    );
+
+    result.map_err(|_| ErrorCode::TokenBurnFailed.into())
+}
+
 pub fn spl_init_token_account(params: InitializeTokenAccountParams<'_, '_>) -> ProgramResult {
     let InitializeTokenAccountParams {
         account,
@@ -194,8 +415,14 @@ pub fn spl_set_authority(params: SetAuthorityParams<'_, '_>) -> ProgramResult {
         owner,
         owner_signer_seeds,
         token_program,
+        verify,
     } = params;
 
+    if verify {
+        guards::assert_owned_by(&account, token_program.key)?;
+        guards::assert_initialized::<spl_token::state::Account>(&account)?;
+    }
+
     let result = invoke_signed(
         &spl_token::instruction::set_authority(
             token_program.key,
@@ -219,8 +446,13 @@ pub fn spl_close_account(params: CloseAccountParams<'_, '_>) -> ProgramResult {
         owner,
         owner_signer_seeds,
         token_program,
+        verify,
     } = params;
 
+    if verify {
+        guards::assert_owned_by(&account, token_program.key)?;
+    }
+
     let result = invoke_signed(
         &spl_token::instruction::close_account(
             token_program.key,
@@ -236,6 +468,21 @@ pub fn spl_close_account(params: CloseAccountParams<'_, '_>) -> ProgramResult {
     result.map_err(|_| ErrorCode::CloseAccountFailed.into())
 }
 
+/// Checks whether `new_pda_account` is safe to (re)allocate as a PDA owned by
+/// `owner`, returning whether it's already sized to `space` (and so the `allocate`
+/// CPI, which rejects any non-empty account, must be skipped).
+fn pda_preconditions(current_owner: &Pubkey, data_len: usize, space: usize) -> Result<bool> {
+    if current_owner != &system_program::ID {
+        return Err(ErrorCode::PdaAlreadyInitialized.into());
+    }
+
+    if data_len != 0 && data_len != space {
+        return Err(ErrorCode::PdaSizeMismatch.into());
+    }
+
+    Ok(data_len == space && space != 0)
+}
+
 pub fn create_pda_account<'a>(
     payer: &AccountInfo<'a>,
     space: usize,
@@ -247,6 +494,9 @@ pub fn create_pda_account<'a>(
     let rent = Rent::get()?;
 
     if new_pda_account.lamports() > 0 {
+        let already_allocated =
+            pda_preconditions(new_pda_account.owner, new_pda_account.data_len(), space)?;
+
         let required_lamports = rent
             .minimum_balance(space)
             .max(1)
@@ -263,11 +513,15 @@ pub fn create_pda_account<'a>(
             )?;
         }
 
-        invoke_signed(
-            &system_instruction::allocate(new_pda_account.key, space as u64),
-            &[new_pda_account.clone(), system_program.clone()],
-            &[new_pda_signer_seeds],
-        )?;
+        // `Allocate` rejects any account whose data isn't empty, even one already
+        // sized to `space`, so skip it when there is nothing left to allocate.
+        if !already_allocated {
+            invoke_signed(
+                &system_instruction::allocate(new_pda_account.key, space as u64),
+                &[new_pda_account.clone(), system_program.clone()],
+                &[new_pda_signer_seeds],
+            )?;
+        }
 
         invoke_signed(
             &system_instruction::assign(new_pda_account.key, owner),
@@ -309,3 +563,65 @@ pub fn close<'info>(info: AccountInfo<'info>, sol_destination: AccountInfo<'info
         .map_err(|_| AccountDidNotSerialize)?;
     Ok(())
 }
+
+pub mod guards {
+    use super::*;
+
+    pub fn assert_initialized<T: Pack + IsInitialized>(account: &AccountInfo) -> Result<T> {
+        let value = T::unpack_unchecked(&account.data.borrow())?;
+        if !value.is_initialized() {
+            Err(ErrorCode::Uninitialized.into())
+        } else {
+            Ok(value)
+        }
+    }
+
+    pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> Result<()> {
+        if account.owner != owner {
+            Err(ErrorCode::IncorrectOwner.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn assert_rent_exempt(rent: &Rent, account: &AccountInfo) -> Result<()> {
+        if !rent.is_exempt(account.lamports(), account.data_len()) {
+            Err(ErrorCode::NotRentExempt.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn assert_token_matching(
+        expected_program: &Pubkey,
+        token_account: &AccountInfo,
+    ) -> Result<()> {
+        assert_owned_by(token_account, expected_program)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pda_preconditions_rejects_account_already_owned_by_target_program() {
+        let owner = Pubkey::new_unique();
+        assert!(pda_preconditions(&owner, 0, 165).is_err());
+    }
+
+    #[test]
+    fn pda_preconditions_rejects_size_mismatch() {
+        assert!(pda_preconditions(&system_program::ID, 100, 165).is_err());
+    }
+
+    #[test]
+    fn pda_preconditions_allows_empty_system_owned_account() {
+        assert!(!pda_preconditions(&system_program::ID, 0, 165).unwrap());
+    }
+
+    #[test]
+    fn pda_preconditions_skips_allocate_when_already_sized() {
+        assert!(pda_preconditions(&system_program::ID, 165, 165).unwrap());
+    }
+}